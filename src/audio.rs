@@ -0,0 +1,297 @@
+use crate::channel::AudioChannel;
+use crate::pitch::Pitch;
+use crate::source::AudioSource;
+use crate::tween::{Easing, StopBehavior, Tween};
+use crate::BeatEvent;
+use bevy::asset::Handle;
+use std::time::Duration;
+use bevy::utils::{HashMap, Uuid};
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+/// Opaque handle to a single playing (or queued) instance.
+///
+/// Returned by every file-backed `play*` method and used to control or stop
+/// that specific sound later. [`Audio::play_pitch`] returns no handle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InstanceHandle {
+    pub(crate) id: Uuid,
+}
+
+impl InstanceHandle {
+    pub(crate) fn new() -> Self {
+        Self { id: Uuid::new_v4() }
+    }
+}
+
+/// The playback state of an [`InstanceHandle`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlaybackState {
+    /// Queued but not yet started by the mixer.
+    Queued,
+    /// Playing, with the current position in seconds.
+    Playing { position: f64 },
+    /// Paused, with the position it will resume from.
+    Paused { position: f64 },
+    /// Stopped and released.
+    Stopped,
+}
+
+/// Settings captured when a file-backed instance is queued.
+pub(crate) struct PlayAudioSettings {
+    pub instance: InstanceHandle,
+    pub source: Handle<AudioSource>,
+    pub channel: AudioChannel,
+    pub looped: bool,
+    pub fade_in: Option<Tween>,
+}
+
+/// A play request waiting for the next beat subdivision before it starts.
+pub(crate) struct QuantizedPlay {
+    pub settings: PlayAudioSettings,
+    /// Fraction of a whole note the start is snapped to (see
+    /// [`BeatEvent::to_subdivision`](crate::BeatEvent)).
+    pub subdivision: f64,
+}
+
+/// Commands drained by `play_queued_audio_system` and applied to the mixer.
+pub(crate) enum AudioCommand {
+    Play(PlayAudioSettings),
+    PlayPitch(Pitch),
+    SetVolume(InstanceHandle, f64),
+    SetVolumeTween(InstanceHandle, f64, Tween),
+    SetChannelVolume(AudioChannel, f64),
+    ApplyEffectiveVolume(f64),
+    SetPanning(InstanceHandle, f64),
+    SetPlaybackRate(InstanceHandle, f64),
+    Pause(InstanceHandle),
+    Resume(InstanceHandle),
+    Stop(InstanceHandle, StopBehavior),
+}
+
+/// The audio control resource.
+///
+/// Methods enqueue commands that are applied once per frame by
+/// `play_queued_audio_system`; none of them touch the mixer directly, so the
+/// resource can be used from any system without `NonSend` access.
+#[derive(Default)]
+pub struct Audio {
+    commands: RwLock<VecDeque<AudioCommand>>,
+    states: RwLock<HashMap<InstanceHandle, PlaybackState>>,
+    channel_volumes: RwLock<HashMap<AudioChannel, f64>>,
+    /// Set when a channel volume changes so `global_volume_system` knows to
+    /// re-apply effective volumes.
+    channel_volume_dirty: RwLock<bool>,
+    pending_quantized: RwLock<VecDeque<QuantizedPlay>>,
+}
+
+impl Audio {
+    fn push(&self, command: AudioCommand) {
+        self.commands.write().unwrap().push_back(command);
+    }
+
+    /// Play `source` once in the default channel.
+    pub fn play(&self, source: Handle<AudioSource>) -> InstanceHandle {
+        self.play_in_channel(source, &AudioChannel::default())
+    }
+
+    /// Play `source` looped in the default channel.
+    pub fn play_looped(&self, source: Handle<AudioSource>) -> InstanceHandle {
+        self.play_looped_in_channel(source, &AudioChannel::default())
+    }
+
+    /// Play `source` once in the default channel, fading its volume in over
+    /// `tween`.
+    pub fn play_with_fade_in(&self, source: Handle<AudioSource>, tween: Tween) -> InstanceHandle {
+        self.queue_play(source, &AudioChannel::default(), false, Some(tween))
+    }
+
+    /// Play `source` once in `channel`.
+    pub fn play_in_channel(
+        &self,
+        source: Handle<AudioSource>,
+        channel: &AudioChannel,
+    ) -> InstanceHandle {
+        self.queue_play(source, channel, false, None)
+    }
+
+    /// Play `source` looped in `channel`.
+    pub fn play_looped_in_channel(
+        &self,
+        source: Handle<AudioSource>,
+        channel: &AudioChannel,
+    ) -> InstanceHandle {
+        self.queue_play(source, channel, true, None)
+    }
+
+    fn queue_play(
+        &self,
+        source: Handle<AudioSource>,
+        channel: &AudioChannel,
+        looped: bool,
+        fade_in: Option<Tween>,
+    ) -> InstanceHandle {
+        let instance = InstanceHandle::new();
+        self.push(AudioCommand::Play(PlayAudioSettings {
+            instance,
+            source,
+            channel: channel.clone(),
+            looped,
+            fade_in,
+        }));
+        instance
+    }
+
+    /// Queue `source` to start on the next `beat` subdivision of the timeline.
+    ///
+    /// The instance does not start until `metronome_events_system` observes the
+    /// beat clock cross the requested subdivision, so the returned handle is
+    /// [`PlaybackState::Queued`] until then.
+    pub fn play_quantized(
+        &self,
+        source: Handle<AudioSource>,
+        channel: &AudioChannel,
+        beat: BeatEvent,
+    ) -> InstanceHandle {
+        let instance = InstanceHandle::new();
+        self.pending_quantized
+            .write()
+            .unwrap()
+            .push_back(QuantizedPlay {
+                settings: PlayAudioSettings {
+                    instance,
+                    source,
+                    channel: channel.clone(),
+                    looped: false,
+                    fade_in: None,
+                },
+                subdivision: beat.to_subdivision(),
+            });
+        self.set_state(instance, PlaybackState::Queued);
+        instance
+    }
+
+    /// Play a procedurally generated [`Pitch`] tone through the stream mixer.
+    ///
+    /// Useful for UI beeps and test tones without shipping an audio file. The
+    /// tone plays for its [`Pitch::duration`], or until the app exits if the
+    /// pitch was created with [`Pitch::looped`].
+    pub fn play_pitch(&self, pitch: Pitch) {
+        self.push(AudioCommand::PlayPitch(pitch));
+    }
+
+    /// Set the volume of `instance`.
+    pub fn set_volume(&self, instance: &InstanceHandle, volume: f32) {
+        self.push(AudioCommand::SetVolume(*instance, volume as f64));
+    }
+
+    /// Ramp the volume of `instance` to `target` over `duration`, shaped by
+    /// `easing`.
+    pub fn set_volume_tween(
+        &self,
+        instance: &InstanceHandle,
+        target: f32,
+        duration: Duration,
+        easing: Easing,
+    ) {
+        self.push(AudioCommand::SetVolumeTween(
+            *instance,
+            target as f64,
+            Tween { duration, easing },
+        ));
+    }
+
+    /// Set the panning of `instance`, where `0.0` is hard left and `1.0` hard
+    /// right.
+    pub fn set_panning(&self, instance: &InstanceHandle, panning: f32) {
+        self.push(AudioCommand::SetPanning(*instance, panning as f64));
+    }
+
+    /// Set the playback rate of `instance`, where `1.0` is native pitch.
+    pub fn set_playback_rate(&self, instance: &InstanceHandle, rate: f64) {
+        self.push(AudioCommand::SetPlaybackRate(*instance, rate));
+    }
+
+    /// Pause `instance`.
+    pub fn pause(&self, instance: &InstanceHandle) {
+        self.push(AudioCommand::Pause(*instance));
+    }
+
+    /// Resume `instance`.
+    pub fn resume(&self, instance: &InstanceHandle) {
+        self.push(AudioCommand::Resume(*instance));
+    }
+
+    /// Stop `instance` immediately.
+    pub fn stop_instance(&self, instance: &InstanceHandle) {
+        self.push(AudioCommand::Stop(*instance, StopBehavior::Immediate));
+    }
+
+    /// Fade `instance` out linearly over `tween.duration`, then release it.
+    /// `tween.easing` is not used.
+    pub fn stop_with_fade_out(&self, instance: &InstanceHandle, tween: Tween) {
+        self.push(AudioCommand::Stop(
+            *instance,
+            StopBehavior::FadeOut(tween.duration),
+        ));
+    }
+
+    /// The stored volume of `channel` (defaults to `1.0`).
+    pub fn channel_volume(&self, channel: &AudioChannel) -> f32 {
+        self.channel_volumes
+            .read()
+            .unwrap()
+            .get(channel)
+            .copied()
+            .unwrap_or(1.0) as f32
+    }
+
+    /// Set the persistent volume of `channel`, re-applying it to every instance
+    /// currently playing in that channel.
+    pub fn set_channel_volume(&self, channel: &AudioChannel, volume: f32) {
+        self.channel_volumes
+            .write()
+            .unwrap()
+            .insert(channel.clone(), volume as f64);
+        *self.channel_volume_dirty.write().unwrap() = true;
+        self.push(AudioCommand::SetChannelVolume(channel.clone(), volume as f64));
+    }
+
+    /// Whether any channel volume changed since this was last called, clearing
+    /// the flag.
+    pub(crate) fn channel_volume_changed(&self) -> bool {
+        let mut dirty = self.channel_volume_dirty.write().unwrap();
+        std::mem::replace(&mut *dirty, false)
+    }
+
+    /// Re-apply `global * channel * instance` to every playing instance.
+    pub(crate) fn apply_effective_volume(&self, global: f64) {
+        self.push(AudioCommand::ApplyEffectiveVolume(global));
+    }
+
+    /// The last known playback state of `instance`.
+    pub fn state(&self, instance: &InstanceHandle) -> PlaybackState {
+        self.states
+            .read()
+            .unwrap()
+            .get(instance)
+            .copied()
+            .unwrap_or(PlaybackState::Stopped)
+    }
+
+    pub(crate) fn drain_commands(&self) -> Vec<AudioCommand> {
+        self.commands.write().unwrap().drain(..).collect()
+    }
+
+    pub(crate) fn take_quantized(&self) -> Vec<QuantizedPlay> {
+        self.pending_quantized.write().unwrap().drain(..).collect()
+    }
+
+    pub(crate) fn requeue_quantized(&self, play: QuantizedPlay) {
+        self.pending_quantized.write().unwrap().push_back(play);
+    }
+
+    pub(crate) fn set_state(&self, instance: InstanceHandle, state: PlaybackState) {
+        self.states.write().unwrap().insert(instance, state);
+    }
+}
@@ -0,0 +1,483 @@
+use crate::audio::{Audio, AudioCommand, InstanceHandle, PlayAudioSettings, PlaybackState};
+use crate::channel::AudioChannel;
+use crate::interpolation::InterpolationMode;
+use crate::stream::{AudioStream, Frame};
+use crate::tween::{Easing, StopBehavior, Tween};
+use crate::{LastTimelineSettings, TimelineSettings, TimelineState};
+use bevy::asset::Assets;
+use bevy::prelude::{Res, ResMut, World};
+use bevy::utils::HashMap;
+use kira::arrangement::handle::ArrangementHandle;
+use kira::arrangement::{Arrangement, LoopArrangementSettings};
+use kira::instance::handle::InstanceHandle as KiraInstance;
+use kira::instance::{
+    InstanceSettings, PauseInstanceSettings, ResumeInstanceSettings, StopInstanceSettings,
+};
+use kira::manager::{AudioManager, AudioManagerSettings};
+use kira::mixer::TrackIndex;
+use kira::parameter::tween::{Easing as KiraEasing, Tween as KiraTween};
+
+/// An in-progress ramp of an instance's base volume.
+struct VolumeTween {
+    start: f64,
+    target: f64,
+    elapsed: f64,
+    tween: Tween,
+}
+
+/// Per-instance state the mixer keeps for later volume/panning changes.
+struct InstanceState {
+    handle: KiraInstance,
+    channel: AudioChannel,
+    base_volume: f64,
+    /// Active software volume ramp, if `set_volume_tween` was called.
+    volume_tween: Option<VolumeTween>,
+}
+
+/// Owns the Kira [`AudioManager`] and all live instance handles.
+///
+/// Every mixer interaction goes through this resource; the [`Audio`] resource
+/// only ever enqueues commands that are applied here once per frame.
+pub struct AudioOutput {
+    manager: Option<AudioManager>,
+    instances: HashMap<InstanceHandle, InstanceState>,
+    channel_volumes: HashMap<AudioChannel, f64>,
+    global_volume: f64,
+    beat_position: f64,
+    /// Seconds since the last re-initialization attempt while the device is
+    /// unavailable.
+    recovery_elapsed: f64,
+}
+
+impl Default for AudioOutput {
+    fn default() -> Self {
+        // A missing audio device must not crash the app; the mixer simply
+        // becomes a no-op until the device comes back (see
+        // [`device_recovery_system`]).
+        let manager = AudioManager::new(AudioManagerSettings::default()).ok();
+        Self {
+            manager,
+            instances: HashMap::default(),
+            channel_volumes: HashMap::default(),
+            global_volume: 1.,
+            beat_position: 0.,
+            recovery_elapsed: 0.,
+        }
+    }
+}
+
+/// How often, in seconds, to retry opening the audio device after a failure.
+const RECOVERY_INTERVAL: f64 = 1.;
+
+impl AudioOutput {
+    fn channel_volume(&self, channel: &AudioChannel) -> f64 {
+        self.channel_volumes.get(channel).copied().unwrap_or(1.)
+    }
+
+    /// `global * channel * instance` — the volume actually sent to the mixer.
+    fn effective_volume(&self, channel: &AudioChannel, base: f64) -> f64 {
+        self.global_volume * self.channel_volume(channel) * base
+    }
+
+    fn play(&mut self, settings: PlayAudioSettings, sources: &Assets<crate::AudioSource>) {
+        let source = match sources.get(&settings.source) {
+            Some(source) => source.clone(),
+            // Asset not loaded yet; the command is dropped like any other play
+            // on a missing handle.
+            None => return,
+        };
+        let base_volume = 1.;
+        let volume = self.effective_volume(&settings.channel, base_volume);
+        let mut instance_settings = InstanceSettings::new().volume(volume);
+        if let Some(fade_in) = settings.fade_in {
+            instance_settings = instance_settings.fade_in_tween(kira_tween(fade_in));
+        }
+
+        let manager = match self.manager.as_mut() {
+            Some(manager) => manager,
+            // No device: drop the play silently, matching the no-op contract.
+            None => return,
+        };
+        let result = if settings.looped {
+            manager
+                .add_sound(source.sound.as_ref().clone())
+                .and_then(|sound| {
+                    manager.add_arrangement(Arrangement::new_loop(
+                        &sound,
+                        LoopArrangementSettings::default(),
+                    ))
+                })
+                .and_then(|mut arrangement: ArrangementHandle| arrangement.play(instance_settings))
+        } else {
+            manager
+                .add_sound(source.sound.as_ref().clone())
+                .and_then(|mut sound| sound.play(instance_settings))
+        };
+
+        if let Ok(handle) = result {
+            self.instances.insert(
+                settings.instance,
+                InstanceState {
+                    handle,
+                    channel: settings.channel,
+                    base_volume,
+                    volume_tween: None,
+                },
+            );
+        }
+    }
+
+    fn set_volume(&mut self, instance: InstanceHandle, base: f64) {
+        // Channel/global factors are read before the mutable borrow below.
+        let value = match self.instances.get(&instance) {
+            Some(state) => self.effective_volume(&state.channel, base),
+            None => return,
+        };
+        if let Some(state) = self.instances.get_mut(&instance) {
+            state.base_volume = base;
+            state.volume_tween = None;
+            let _ = state.handle.set_volume(kira::Value::Fixed(value));
+        }
+    }
+
+    fn set_volume_tween(&mut self, instance: InstanceHandle, target: f64, tween: Tween) {
+        if let Some(state) = self.instances.get_mut(&instance) {
+            // A zero-length ramp is just an immediate set.
+            if tween.duration.is_zero() {
+                drop(state);
+                self.set_volume(instance, target);
+                return;
+            }
+            let start = state.base_volume;
+            state.volume_tween = Some(VolumeTween {
+                start,
+                target,
+                elapsed: 0.,
+                tween,
+            });
+        }
+    }
+
+    /// Advance every active volume ramp by `dt` seconds, applying the eased
+    /// value and clearing ramps that have finished.
+    fn advance_volume_tweens(&mut self, dt: f64) {
+        let finished: Vec<InstanceHandle> = self
+            .instances
+            .iter_mut()
+            .filter_map(|(handle, state)| {
+                let tween = state.volume_tween.as_mut()?;
+                tween.elapsed += dt;
+                let duration = tween.tween.duration.as_secs_f64();
+                let progress = (tween.elapsed / duration).clamp(0., 1.);
+                let eased = tween.tween.easing.ease(progress);
+                state.base_volume = tween.start + (tween.target - tween.start) * eased;
+                if tween.elapsed >= duration {
+                    Some(*handle)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // Re-apply the new base volumes (which also folds channel/global gain).
+        let handles: Vec<InstanceHandle> = self
+            .instances
+            .iter()
+            .filter(|(_, state)| state.volume_tween.is_some())
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in handles {
+            let base = self.instances[&handle].base_volume;
+            let value = self.effective_volume(&self.instances[&handle].channel, base);
+            if let Some(state) = self.instances.get_mut(&handle) {
+                let _ = state.handle.set_volume(kira::Value::Fixed(value));
+            }
+        }
+        for handle in finished {
+            if let Some(state) = self.instances.get_mut(&handle) {
+                state.volume_tween = None;
+            }
+        }
+    }
+
+    fn set_channel_volume(&mut self, channel: AudioChannel, volume: f64) {
+        self.channel_volumes.insert(channel.clone(), volume);
+        self.reapply_channel(&channel);
+    }
+
+    fn apply_effective_volume(&mut self, global: f64) {
+        self.global_volume = global;
+        self.reapply_all();
+    }
+
+    fn reapply_channel(&mut self, channel: &AudioChannel) {
+        let handles: Vec<InstanceHandle> = self
+            .instances
+            .iter()
+            .filter(|(_, state)| &state.channel == channel)
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in handles {
+            let base = self.instances[&handle].base_volume;
+            self.set_volume(handle, base);
+        }
+    }
+
+    fn reapply_all(&mut self) {
+        let handles: Vec<InstanceHandle> = self.instances.keys().copied().collect();
+        for handle in handles {
+            let base = self.instances[&handle].base_volume;
+            self.set_volume(handle, base);
+        }
+    }
+
+    fn set_panning(&mut self, instance: InstanceHandle, panning: f64) {
+        if let Some(state) = self.instances.get_mut(&instance) {
+            let _ = state.handle.set_panning(panning);
+        }
+    }
+
+    fn set_playback_rate(&mut self, instance: InstanceHandle, rate: f64) {
+        if let Some(state) = self.instances.get_mut(&instance) {
+            let _ = state.handle.set_playback_rate(rate);
+        }
+    }
+
+    fn pause(&mut self, instance: InstanceHandle) {
+        if let Some(state) = self.instances.get_mut(&instance) {
+            let _ = state.handle.pause(PauseInstanceSettings::default());
+        }
+    }
+
+    fn resume(&mut self, instance: InstanceHandle) {
+        if let Some(state) = self.instances.get_mut(&instance) {
+            let _ = state.handle.resume(ResumeInstanceSettings::default());
+        }
+    }
+
+    fn stop(&mut self, instance: InstanceHandle, behavior: StopBehavior) {
+        if let Some(mut state) = self.instances.remove(&instance) {
+            let settings = match behavior {
+                StopBehavior::Immediate => StopInstanceSettings::default(),
+                StopBehavior::FadeOut(duration) => StopInstanceSettings::default()
+                    .fade_tween(kira_tween(Tween::linear(duration))),
+            };
+            let _ = state.handle.stop(settings);
+        }
+    }
+
+    /// Feed a stream into the mixer, blending its successive frames with
+    /// `interpolation`.
+    pub fn start_stream<T: AudioStream>(&mut self, stream: T, interpolation: InterpolationMode) {
+        if let Some(manager) = self.manager.as_mut() {
+            let _ = manager.add_stream(
+                TrackIndex::Main,
+                StreamAdapter::new(stream, interpolation),
+            );
+        }
+    }
+
+    /// Attempt to reopen the audio device. Returns `true` once a manager is
+    /// available again.
+    fn try_reinitialize(&mut self) -> bool {
+        if self.manager.is_some() {
+            return true;
+        }
+        match AudioManager::new(AudioManagerSettings::default()) {
+            Ok(manager) => {
+                self.manager = Some(manager);
+                // Instances from before the loss cannot be resumed; drop their
+                // stale handles so new plays start cleanly.
+                self.instances.clear();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Adapts a user [`AudioStream`] to Kira's stream trait, blending successive
+/// source frames according to the configured [`InterpolationMode`].
+struct StreamAdapter<T: AudioStream> {
+    stream: T,
+    interpolation: InterpolationMode,
+    /// The previously produced source frame, blended with the next one.
+    previous: Frame,
+    /// Whether `previous` holds a real frame yet.
+    primed: bool,
+}
+
+impl<T: AudioStream> StreamAdapter<T> {
+    fn new(stream: T, interpolation: InterpolationMode) -> Self {
+        Self {
+            stream,
+            interpolation,
+            previous: Frame::default(),
+            primed: false,
+        }
+    }
+}
+
+impl<T: AudioStream> kira::audio_stream::AudioStream for StreamAdapter<T> {
+    fn next(&mut self, dt: f64) -> kira::Frame {
+        let current = self.stream.next(1. / dt.max(f64::EPSILON));
+        // The first call has no predecessor to blend with; emit it verbatim.
+        let blended = if self.primed {
+            self.interpolation.sample(self.previous, current, 0.5)
+        } else {
+            self.primed = true;
+            current
+        };
+        self.previous = current;
+        kira::Frame::new(blended.left, blended.right)
+    }
+}
+
+/// Translate our [`Tween`] into the Kira tween the mixer understands.
+fn kira_tween(tween: Tween) -> KiraTween {
+    let easing = match tween.easing {
+        Easing::Linear => KiraEasing::Linear,
+        Easing::InPowi(power) => KiraEasing::InPowi(power),
+        Easing::OutPowi(power) => KiraEasing::OutPowi(power),
+    };
+    KiraTween {
+        duration: tween.duration.as_secs_f64(),
+        easing,
+        ..Default::default()
+    }
+}
+
+/// Nothing to set up on the mixer side; the beat clock is advanced from `Time`.
+pub fn init_metronome_system() {}
+
+/// Apply every queued [`Audio`] command to the mixer once per frame.
+pub fn play_queued_audio_system(
+    time: Res<bevy::core::Time>,
+    mut audio_output: bevy::prelude::NonSendMut<AudioOutput>,
+    audio: Res<Audio>,
+    sources: Res<Assets<crate::AudioSource>>,
+) {
+    for command in audio.drain_commands() {
+        match command {
+            AudioCommand::Play(settings) => audio_output.play(settings, &sources),
+            AudioCommand::PlayPitch(pitch) => {
+                audio_output.start_stream(pitch, InterpolationMode::default())
+            }
+            AudioCommand::SetVolume(instance, volume) => audio_output.set_volume(instance, volume),
+            AudioCommand::SetVolumeTween(instance, target, tween) => {
+                audio_output.set_volume_tween(instance, target, tween)
+            }
+            AudioCommand::SetChannelVolume(channel, volume) => {
+                audio_output.set_channel_volume(channel, volume)
+            }
+            AudioCommand::ApplyEffectiveVolume(global) => {
+                audio_output.apply_effective_volume(global)
+            }
+            AudioCommand::SetPanning(instance, panning) => {
+                audio_output.set_panning(instance, panning)
+            }
+            AudioCommand::SetPlaybackRate(instance, rate) => {
+                audio_output.set_playback_rate(instance, rate)
+            }
+            AudioCommand::Pause(instance) => audio_output.pause(instance),
+            AudioCommand::Resume(instance) => audio_output.resume(instance),
+            AudioCommand::Stop(instance, behavior) => audio_output.stop(instance, behavior),
+        }
+    }
+
+    audio_output.advance_volume_tweens(time.delta_seconds_f64());
+}
+
+/// Mirror each live instance's Kira playback state back into [`Audio`].
+pub fn update_instance_states_system(world: &mut World) {
+    let states: Vec<(InstanceHandle, PlaybackState)> =
+        match world.get_non_send_resource::<AudioOutput>() {
+            Some(output) => output
+                .instances
+                .iter()
+                .map(|(handle, state)| (*handle, kira_state(&state.handle)))
+                .collect(),
+            None => return,
+        };
+    if let Some(audio) = world.get_resource::<Audio>() {
+        for (handle, state) in states {
+            audio.set_state(handle, state);
+        }
+    }
+}
+
+fn kira_state(handle: &KiraInstance) -> PlaybackState {
+    use kira::instance::InstanceState as KiraInstanceState;
+    match handle.state() {
+        KiraInstanceState::Playing => PlaybackState::Playing {
+            position: handle.position(),
+        },
+        KiraInstanceState::Paused(_) => PlaybackState::Paused {
+            position: handle.position(),
+        },
+        KiraInstanceState::Stopped | KiraInstanceState::Stopping(_) => PlaybackState::Stopped,
+    }
+}
+
+/// Periodically try to open the audio device when no mixer could be created.
+///
+/// While no device is available the mixer is a no-op; this system retries every
+/// [`RECOVERY_INTERVAL`] seconds so that plays issued after the device returns
+/// are heard. Failure of a device that was already opened is not detected, and
+/// sounds requested while no device was available are dropped, not replayed.
+pub fn device_recovery_system(
+    time: Res<bevy::core::Time>,
+    mut audio_output: bevy::prelude::NonSendMut<AudioOutput>,
+) {
+    if audio_output.manager.is_some() {
+        audio_output.recovery_elapsed = 0.;
+        return;
+    }
+    audio_output.recovery_elapsed += time.delta_seconds_f64();
+    if audio_output.recovery_elapsed >= RECOVERY_INTERVAL {
+        audio_output.recovery_elapsed = 0.;
+        audio_output.try_reinitialize();
+    }
+}
+
+/// Advance the beat clock and start any quantized plays whose subdivision was
+/// crossed this frame.
+pub fn metronome_events_system(
+    time: Res<bevy::core::Time>,
+    timeline: Res<TimelineSettings>,
+    mut last: ResMut<LastTimelineSettings>,
+    audio: Res<Audio>,
+    sources: Res<Assets<crate::AudioSource>>,
+    mut audio_output: bevy::prelude::NonSendMut<AudioOutput>,
+) {
+    if timeline.state != last.inner.state {
+        if timeline.state == TimelineState::Stopped {
+            audio_output.beat_position = 0.;
+        }
+        last.inner.state = timeline.state.clone();
+    }
+    last.inner.bpm = timeline.bpm;
+
+    if timeline.state != TimelineState::Playing {
+        return;
+    }
+
+    let previous = audio_output.beat_position;
+    audio_output.beat_position += time.delta_seconds_f64() * timeline.bpm / 60.;
+    let current = audio_output.beat_position;
+
+    for play in audio.take_quantized() {
+        if crossed_subdivision(previous, current, play.subdivision) {
+            audio_output.play(play.settings, &sources);
+        } else {
+            audio.requeue_quantized(play);
+        }
+    }
+}
+
+/// Whether the beat clock moved across a multiple of `subdivision` between
+/// `previous` and `current`. A non-positive subdivision (`BeatEvent::Whole`)
+/// snaps to whole beats.
+fn crossed_subdivision(previous: f64, current: f64, subdivision: f64) -> bool {
+    let subdivision = if subdivision <= 0. { 1. } else { subdivision };
+    (previous / subdivision).floor() < (current / subdivision).floor()
+}
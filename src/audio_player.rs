@@ -0,0 +1,103 @@
+use crate::audio::InstanceHandle;
+use crate::source::AudioSource;
+use crate::Audio;
+use bevy::asset::Handle;
+use bevy::ecs::removal_detection::RemovedComponents;
+use bevy::prelude::{Added, Commands, Component, Entity, Query, Res, ResMut};
+use bevy::utils::HashMap;
+
+/// Component that starts playing an [`AudioSource`] when added to an entity.
+///
+/// Spawning an entity with an `AudioPlayer` is the ECS-native counterpart to
+/// calling [`Audio::play`](crate::Audio::play): the sound's lifetime follows
+/// the entity, so despawning the entity (or removing the component) stops the
+/// instance.
+#[derive(Component)]
+pub struct AudioPlayer(pub Handle<AudioSource>);
+
+/// How a looped [`AudioPlayer`] behaves when it reaches the end of the source.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopBehavior {
+    /// Play once and stop.
+    Once,
+    /// Restart from the beginning when the source ends.
+    Loop,
+}
+
+impl Default for LoopBehavior {
+    fn default() -> Self {
+        Self::Once
+    }
+}
+
+/// Settings applied to an [`AudioPlayer`] when it starts.
+#[derive(Component, Clone)]
+pub struct PlaybackSettings {
+    /// Initial volume of the instance.
+    pub volume: f32,
+    /// Playback rate, where `1.0` is the source's native pitch.
+    pub playback_rate: f64,
+    /// Whether the source loops or plays once.
+    pub loop_behavior: LoopBehavior,
+    /// Start the instance paused.
+    pub paused: bool,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.,
+            playback_rate: 1.,
+            loop_behavior: LoopBehavior::default(),
+            paused: false,
+        }
+    }
+}
+
+/// Handle of the Kira instance started for an [`AudioPlayer`] entity.
+#[derive(Component)]
+pub struct PlayingSound(pub InstanceHandle);
+
+/// Maps each [`AudioPlayer`] entity to its instance so the instance can still
+/// be stopped after the entity (and its [`PlayingSound`]) is despawned.
+#[derive(Default)]
+pub struct AudioPlayerInstances(HashMap<Entity, InstanceHandle>);
+
+/// Start a Kira instance for every newly-added [`AudioPlayer`], remembering it
+/// both on the entity and in [`AudioPlayerInstances`].
+pub fn play_audio_player_system(
+    mut commands: Commands,
+    audio: Res<Audio>,
+    mut instances: ResMut<AudioPlayerInstances>,
+    players: Query<(Entity, &AudioPlayer, Option<&PlaybackSettings>), Added<AudioPlayer>>,
+) {
+    for (entity, player, settings) in players.iter() {
+        let settings = settings.cloned().unwrap_or_default();
+        let instance = match settings.loop_behavior {
+            LoopBehavior::Loop => audio.play_looped(player.0.clone()),
+            LoopBehavior::Once => audio.play(player.0.clone()),
+        };
+        audio.set_volume(&instance, settings.volume);
+        audio.set_playback_rate(&instance, settings.playback_rate);
+        if settings.paused {
+            audio.pause(&instance);
+        }
+        instances.0.insert(entity, instance);
+        commands.entity(entity).insert(PlayingSound(instance));
+    }
+}
+
+/// Stop the Kira instances belonging to [`AudioPlayer`] entities whose
+/// component was removed — including on despawn, where the [`PlayingSound`]
+/// component is already gone but the handle is still cached here.
+pub fn cleanup_audio_player_system(
+    audio: Res<Audio>,
+    mut instances: ResMut<AudioPlayerInstances>,
+    mut removed: RemovedComponents<AudioPlayer>,
+) {
+    for entity in removed.iter() {
+        if let Some(instance) = instances.0.remove(&entity) {
+            audio.stop_instance(&instance);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::borrow::Cow;
+
+/// Identifies a named mixer channel.
+///
+/// Every sound is played in a channel (the default one unless a
+/// `*_in_channel` method is used). Each channel can be given a persistent
+/// volume independently of the others; pausing and stopping act on single
+/// instances, not on whole channels.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AudioChannel {
+    key: Cow<'static, str>,
+}
+
+impl AudioChannel {
+    /// Create a channel handle from a key.
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// The channel's key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Default for AudioChannel {
+    fn default() -> Self {
+        Self {
+            key: Cow::Borrowed("default"),
+        }
+    }
+}
@@ -33,20 +33,38 @@
 #![feature(const_fn_floating_point_arithmetic)]
 
 pub use audio::{Audio, InstanceHandle, PlaybackState};
+pub use audio_player::{AudioPlayer, LoopBehavior, PlaybackSettings, PlayingSound};
 pub use channel::AudioChannel;
+pub use interpolation::InterpolationMode;
+pub use pitch::{Pitch, Waveform};
 pub use source::AudioSource;
+pub use spatial::{AudioEmitter, AudioReceiver, SpatialAudioChannel, SpatialScale};
 pub use stream::{AudioStream, Frame, StreamedAudio};
+pub use tween::{Easing, StopBehavior, Tween};
+pub use volume::GlobalVolume;
 
 mod audio;
 mod audio_output;
+mod audio_player;
 mod channel;
+mod interpolation;
+mod pitch;
 mod source;
+mod spatial;
 mod stream;
+mod tween;
+mod volume;
 
 use crate::audio_output::{
-    init_metronome_system, metronome_events_system, play_queued_audio_system, stream_audio_system,
-    update_instance_states_system, AudioOutput,
+    device_recovery_system, init_metronome_system, metronome_events_system,
+    play_queued_audio_system, update_instance_states_system, AudioOutput,
 };
+use crate::audio_player::{
+    cleanup_audio_player_system, play_audio_player_system, AudioPlayerInstances,
+};
+use crate::spatial::spatial_audio_system;
+use crate::stream::stream_audio_system;
+use crate::volume::global_volume_system;
 
 #[cfg(feature = "flac")]
 use crate::source::FlacLoader;
@@ -216,7 +234,16 @@ impl Plugin for AudioPlugin {
         app.init_resource::<LastTimelineSettings>()
             .init_resource::<TimelineSettings>()
             .init_resource::<Audio>()
+            .init_resource::<SpatialScale>()
+            .init_resource::<SpatialAudioChannel>()
+            .init_resource::<GlobalVolume>()
+            .init_resource::<AudioPlayerInstances>()
+            .add_system_to_stage(CoreStage::PreUpdate, device_recovery_system)
             .add_system_to_stage(CoreStage::PreUpdate, metronome_events_system)
+            .add_system_to_stage(CoreStage::PostUpdate, play_audio_player_system)
+            .add_system_to_stage(CoreStage::PostUpdate, cleanup_audio_player_system)
+            .add_system_to_stage(CoreStage::PostUpdate, spatial_audio_system)
+            .add_system_to_stage(CoreStage::PostUpdate, global_volume_system)
             .add_system_to_stage(CoreStage::PostUpdate, play_queued_audio_system)
             .add_system_to_stage(
                 CoreStage::PreUpdate,
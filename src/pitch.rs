@@ -0,0 +1,90 @@
+use crate::stream::{AudioStream, Frame};
+use std::time::Duration;
+
+/// Waveform emitted by a [`Pitch`] generator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Waveform {
+    /// A smooth sine tone.
+    Sine,
+    /// A harsh square tone.
+    Square,
+}
+
+impl Default for Waveform {
+    fn default() -> Self {
+        Self::Sine
+    }
+}
+
+/// A pure tone of a given frequency and duration.
+///
+/// Fed into the stream machinery by [`Audio::play_pitch`](crate::Audio::play_pitch)
+/// so games can emit UI beeps, test tones, or simple synth notes without
+/// shipping audio files. A `None` duration plays indefinitely.
+#[derive(Debug, Clone)]
+pub struct Pitch {
+    /// Frequency of the tone in Hertz.
+    pub frequency: f64,
+    /// How long to play before going silent, or `None` to loop forever.
+    pub duration: Option<Duration>,
+    /// Shape of the generated wave.
+    pub waveform: Waveform,
+    phase: f64,
+    elapsed: f64,
+}
+
+impl Pitch {
+    /// Create a tone that plays `frequency` Hz for `duration`.
+    pub fn new(frequency: f64, duration: Duration) -> Self {
+        Self {
+            frequency,
+            duration: Some(duration),
+            waveform: Waveform::default(),
+            phase: 0.,
+            elapsed: 0.,
+        }
+    }
+
+    /// Create a tone that plays `frequency` Hz with no end.
+    pub fn looped(frequency: f64) -> Self {
+        Self {
+            frequency,
+            duration: None,
+            waveform: Waveform::default(),
+            phase: 0.,
+            elapsed: 0.,
+        }
+    }
+}
+
+impl AudioStream for Pitch {
+    fn next(&mut self, sample_rate: f64) -> Frame {
+        if let Some(duration) = self.duration {
+            if self.elapsed >= duration.as_secs_f64() {
+                return Frame {
+                    left: 0.,
+                    right: 0.,
+                };
+            }
+        }
+
+        self.phase = (self.phase + self.frequency / sample_rate).fract();
+        self.elapsed += 1.0 / sample_rate;
+
+        let sample = match self.waveform {
+            Waveform::Sine => (self.phase * 2.0 * std::f64::consts::PI).sin(),
+            Waveform::Square => {
+                if self.phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        } as f32;
+
+        Frame {
+            left: sample,
+            right: sample,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use kira::sound::{Sound, SoundSettings};
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// A loaded piece of audio, shared by every instance playing it.
+#[derive(Clone, TypeUuid)]
+#[uuid = "6a9fc4ca-b5b5-4f8c-8d2d-7a4b3c2d1e0f"]
+pub struct AudioSource {
+    /// The decoded Kira sound backing this asset.
+    pub sound: Arc<Sound>,
+}
+
+/// Loader for `ogg` encoded assets.
+#[cfg(feature = "ogg")]
+#[derive(Default)]
+pub struct OggLoader;
+
+#[cfg(feature = "ogg")]
+impl AssetLoader for OggLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let sound = Sound::from_ogg_reader(Cursor::new(bytes), SoundSettings::default())?;
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                sound: Arc::new(sound),
+            }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ogg"]
+    }
+}
+
+/// Loader for `mp3` encoded assets.
+#[cfg(feature = "mp3")]
+#[derive(Default)]
+pub struct Mp3Loader;
+
+#[cfg(feature = "mp3")]
+impl AssetLoader for Mp3Loader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let sound = Sound::from_mp3_reader(Cursor::new(bytes), SoundSettings::default())?;
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                sound: Arc::new(sound),
+            }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["mp3"]
+    }
+}
+
+/// Loader for `wav` encoded assets.
+#[cfg(feature = "wav")]
+#[derive(Default)]
+pub struct WavLoader;
+
+#[cfg(feature = "wav")]
+impl AssetLoader for WavLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let sound = Sound::from_wav_reader(Cursor::new(bytes), SoundSettings::default())?;
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                sound: Arc::new(sound),
+            }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["wav"]
+    }
+}
+
+/// Loader for `flac` encoded assets.
+#[cfg(feature = "flac")]
+#[derive(Default)]
+pub struct FlacLoader;
+
+#[cfg(feature = "flac")]
+impl AssetLoader for FlacLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let sound = Sound::from_flac_reader(Cursor::new(bytes), SoundSettings::default())?;
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                sound: Arc::new(sound),
+            }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["flac"]
+    }
+}
+
+/// Loader that reads Kira [`SoundSettings`] from a `*.{ext}.ron` sidecar.
+#[cfg(feature = "settings_loader")]
+#[derive(Default)]
+pub struct SettingsLoader;
+
+#[cfg(feature = "settings_loader")]
+impl AssetLoader for SettingsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let sound = Sound::from_file(load_context.path(), ron::de::from_bytes(bytes)?)?;
+            load_context.set_default_asset(LoadedAsset::new(AudioSource {
+                sound: Arc::new(sound),
+            }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ogg.ron", "mp3.ron", "wav.ron", "flac.ron"]
+    }
+}
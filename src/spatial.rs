@@ -0,0 +1,88 @@
+use crate::audio::InstanceHandle;
+use crate::channel::AudioChannel;
+use crate::Audio;
+use bevy::prelude::{Component, GlobalTransform, Query, Res, Vec3, With, Without};
+use bevy::reflect::TypeUuid;
+
+/// The mixer channel that spatial sounds are played in.
+///
+/// Emitter instances should be started in this channel so that its stored
+/// volume (set via [`Audio::set_channel_volume`](crate::Audio::set_channel_volume))
+/// acts as a master control for all spatial audio. [`spatial_audio_system`]
+/// drives only per-emitter attenuation and panning; the channel gain is applied
+/// by the mixer on top of it.
+#[derive(TypeUuid)]
+#[uuid = "2c1b0a9d-8e7f-4a6b-9c5d-3e2f1a0b9c8d"]
+pub struct SpatialAudioChannel(pub AudioChannel);
+
+impl Default for SpatialAudioChannel {
+    fn default() -> Self {
+        Self(AudioChannel::new("spatial"))
+    }
+}
+
+/// Marker component for the entity whose transform is used as the spatial
+/// listener.
+///
+/// Attach this to the entity that should "hear" spatial audio (usually the
+/// camera or player). Only the first receiver found is used each frame.
+#[derive(Component, Default)]
+pub struct AudioReceiver;
+
+/// Component holding the playing instances that should be positioned in the
+/// world relative to the [`AudioReceiver`].
+///
+/// Add the handles returned by the [`Audio`](crate::Audio) API so that the
+/// spatial system can drive their volume and panning from the owning entity's
+/// global transform.
+#[derive(Component, Default)]
+pub struct AudioEmitter {
+    /// The instances to position relative to the receiver.
+    pub instances: Vec<InstanceHandle>,
+}
+
+/// Scales the distance used by the spatial attenuation curve.
+///
+/// A larger value makes sounds fall off faster with distance.
+#[derive(TypeUuid)]
+#[uuid = "9c7e0b2c-0f5d-4d8a-9d4a-2b6c2e7f1a10"]
+pub struct SpatialScale(pub f32);
+
+impl Default for SpatialScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Drive volume and panning of every [`AudioEmitter`] instance from its global
+/// transform relative to the [`AudioReceiver`].
+pub fn spatial_audio_system(
+    audio: Res<Audio>,
+    scale: Res<SpatialScale>,
+    receiver: Query<&GlobalTransform, With<AudioReceiver>>,
+    emitters: Query<(&GlobalTransform, &AudioEmitter), Without<AudioReceiver>>,
+) {
+    let receiver = match receiver.iter().next() {
+        Some(receiver) => receiver,
+        None => return,
+    };
+    let receiver_pos = receiver.translation();
+    let receiver_right = receiver.right();
+
+    for (emitter_transform, emitter) in emitters.iter() {
+        let d = emitter_transform.translation() - receiver_pos;
+        // Per-emitter attenuation only; the master volume of the spatial
+        // channel the instances play in is folded by the mixer.
+        let volume = (1.0 / (1.0 + (d.length() * scale.0).powi(2))).clamp(0.0, 1.0);
+        let panning = if d == Vec3::ZERO {
+            0.5
+        } else {
+            0.5 + 0.5 * d.normalize().dot(receiver_right).clamp(-1.0, 1.0)
+        };
+
+        for instance in &emitter.instances {
+            audio.set_volume(instance, volume);
+            audio.set_panning(instance, panning);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::audio_output::AudioOutput;
+use crate::interpolation::InterpolationMode;
+use bevy::prelude::{NonSendMut, ResMut};
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+/// A single stereo audio frame.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Frame {
+    /// Sample for the left channel.
+    pub left: f32,
+    /// Sample for the right channel.
+    pub right: f32,
+}
+
+/// A user-provided source of audio frames.
+///
+/// Implement this to feed procedurally generated audio into the mixer. `next`
+/// is called once per output frame; `sample_rate` is the rate the stream is
+/// expected to produce frames at.
+pub trait AudioStream: Send + 'static {
+    /// Produce the next frame for the given source sample rate.
+    fn next(&mut self, sample_rate: f64) -> Frame;
+}
+
+/// Resource used to start [`AudioStream`]s of a particular type.
+///
+/// Streams pushed here are handed to the mixer by [`stream_audio_system`]. The
+/// mixer pulls one stream frame per output frame at the device's rate; the
+/// stored [`InterpolationMode`] controls how each pulled frame is blended with
+/// the previous one. No sample-rate conversion is done.
+pub struct StreamedAudio<T: AudioStream> {
+    commands: RwLock<VecDeque<T>>,
+    /// Blending mode applied to every stream started through this resource.
+    pub interpolation: InterpolationMode,
+}
+
+impl<T: AudioStream> Default for StreamedAudio<T> {
+    fn default() -> Self {
+        Self {
+            commands: RwLock::new(VecDeque::new()),
+            interpolation: InterpolationMode::default(),
+        }
+    }
+}
+
+impl<T: AudioStream> StreamedAudio<T> {
+    /// Queue `stream` to start playing on the next update.
+    pub fn stream(&self, stream: T) {
+        self.commands.write().unwrap().push_back(stream);
+    }
+
+    /// Set the blending mode for streams started afterwards.
+    pub fn set_interpolation(&mut self, interpolation: InterpolationMode) {
+        self.interpolation = interpolation;
+    }
+}
+
+/// Hand every queued stream to the mixer, tagging it with the resource's
+/// [`InterpolationMode`] so the resampler knows how to blend source frames.
+pub fn stream_audio_system<T: AudioStream>(
+    mut audio_output: NonSendMut<AudioOutput>,
+    streams: ResMut<StreamedAudio<T>>,
+) {
+    let interpolation = streams.interpolation;
+    let mut commands = streams.commands.write().unwrap();
+    for stream in commands.drain(..) {
+        audio_output.start_stream(stream, interpolation);
+    }
+}
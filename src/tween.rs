@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// Easing curve used when tweening a value towards a target.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    /// Constant rate of change.
+    Linear,
+    /// Accelerating curve; larger powers ease in more sharply.
+    InPowi(i32),
+    /// Decelerating curve; larger powers ease out more sharply.
+    OutPowi(i32),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Easing {
+    /// Map a linear progress `t` in `[0, 1]` onto the eased curve.
+    pub(crate) fn ease(&self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::InPowi(power) => t.powi(*power),
+            Self::OutPowi(power) => 1. - (1. - t).powi(*power),
+        }
+    }
+}
+
+/// A tween towards a target value over a fixed duration.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tween {
+    /// Time to reach the target value.
+    pub duration: Duration,
+    /// Shape of the interpolation.
+    pub easing: Easing,
+}
+
+impl Tween {
+    /// A linear tween over the given duration.
+    pub fn linear(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+}
+
+/// How an instance is released when stopping.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StopBehavior {
+    /// Release the instance immediately.
+    Immediate,
+    /// Ramp volume to zero over the given duration, then release.
+    FadeOut(Duration),
+}
+
+impl Default for StopBehavior {
+    fn default() -> Self {
+        Self::Immediate
+    }
+}
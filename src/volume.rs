@@ -0,0 +1,27 @@
+use crate::Audio;
+use bevy::prelude::Res;
+use bevy::reflect::TypeUuid;
+
+/// Master volume applied multiplicatively on top of each channel's and each
+/// instance's own volume.
+///
+/// Changing this (or any channel's stored volume) re-applies the effective
+/// volume `global * channel * instance` to every currently-playing instance,
+/// so it can back a master volume slider that affects already-playing audio.
+#[derive(TypeUuid)]
+#[uuid = "f0a6d7e1-3b2c-4d5e-8f09-1a2b3c4d5e6f"]
+pub struct GlobalVolume(pub f64);
+
+impl Default for GlobalVolume {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Re-apply the effective volume of every playing instance whenever the
+/// [`GlobalVolume`] or any channel's stored volume changes.
+pub fn global_volume_system(global: Res<GlobalVolume>, audio: Res<Audio>) {
+    if global.is_changed() || audio.channel_volume_changed() {
+        audio.apply_effective_volume(global.0);
+    }
+}